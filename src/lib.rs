@@ -20,11 +20,177 @@ macro_rules! log {
     }
 }
 
+// A rule is represented as two bitmasks, `birth` and `survival`, where bit
+// `n` (0..=8) is set if a cell is born/survives with exactly `n` live
+// neighbors. This mirrors the B/S notation used to describe Life-like
+// cellular automata (e.g. `B3/S23` for Conway's Life, `B36/S23` for
+// HighLife).
+#[derive(Clone, Copy, Debug)]
+struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: (1 << 2) | (1 << 3),
+    };
+
+    // Parse a standard B/S rule string such as "B3/S23". Unrecognized
+    // characters are ignored, so a malformed string simply yields an empty
+    // segment rather than an error.
+    fn parse(rule: &str) -> Rule {
+        let mut birth = 0u16;
+        let mut survival = 0u16;
+        let mut in_birth = false;
+        let mut in_survival = false;
+
+        for c in rule.chars() {
+            match c {
+                'B' | 'b' => {
+                    in_birth = true;
+                    in_survival = false;
+                }
+                'S' | 's' => {
+                    in_survival = true;
+                    in_birth = false;
+                }
+                '/' => {
+                    in_birth = false;
+                    in_survival = false;
+                }
+                '0'..='8' => {
+                    let n = c.to_digit(10).unwrap();
+                    if in_birth {
+                        birth |= 1 << n;
+                    } else if in_survival {
+                        survival |= 1 << n;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Rule { birth, survival }
+    }
+
+    // Render back to B/S notation, e.g. `"B3/S23"`.
+    fn to_bs_string(&self) -> String {
+        let digits = |mask: u16| {
+            (0u16..=8)
+                .filter(|n| mask & (1 << n) != 0)
+                .map(|n| n.to_string())
+                .collect::<String>()
+        };
+        format!("B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+/// A named Life pattern that can be stamped into a universe with
+/// `Universe::insert_pattern`, anchored at its top-left corner (or, for
+/// `Glider`/`Pulsar`, its center, matching their original `draw_*` helpers).
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    Glider,
+    Pulsar,
+    Copperhead,
+    GosperGliderGun,
+    Lwss,
+    Block,
+    Beehive,
+}
+
+const GLIDER: &[(i32, i32)] = &[(0, 0), (-1, -1), (0, 1), (1, 0), (1, -1)];
+
+const PULSAR: &[(i32, i32)] = &[
+    (-6, -4), (-6, -3), (-6, -2), (-6, 4), (-6, 3), (-6, 2),
+    (-4, -6), (-4, -1), (-4, 1), (-4, 6),
+    (-3, -6), (-3, -1), (-3, 1), (-3, 6),
+    (-2, -6), (-2, -1), (-2, 1), (-2, 6),
+    (-1, -4), (-1, -3), (-1, -2), (-1, 4), (-1, 3), (-1, 2),
+    (1, -4), (1, -3), (1, -2), (1, 4), (1, 3), (1, 2),
+    (2, -6), (2, -1), (2, 1), (2, 6),
+    (3, -6), (3, -1), (3, 1), (3, 6),
+    (4, -6), (4, -1), (4, 1), (4, 6),
+    (6, -4), (6, -3), (6, -2), (6, 4), (6, 3), (6, 2),
+];
+
+const COPPERHEAD: &[(i32, i32)] = &[
+    (0, 1), (0, 2), (0, 5), (0, 6),
+    (1, 3), (1, 4),
+    (2, 3), (2, 4),
+    (3, 0), (3, 2), (3, 5), (3, 7),
+    (4, 0), (4, 7),
+    (6, 0), (6, 7),
+    (7, 1), (7, 2), (7, 5), (7, 6),
+    (8, 2), (8, 3), (8, 4), (8, 5),
+    (10, 3), (10, 4),
+    (11, 3), (11, 4),
+];
+
+const GOSPER_GLIDER_GUN: &[(i32, i32)] = &[
+    (0, 24),
+    (1, 22), (1, 24),
+    (2, 12), (2, 13), (2, 20), (2, 21), (2, 34), (2, 35),
+    (3, 11), (3, 15), (3, 20), (3, 21), (3, 34), (3, 35),
+    (4, 0), (4, 1), (4, 10), (4, 16), (4, 20), (4, 21),
+    (5, 0), (5, 1), (5, 10), (5, 14), (5, 16), (5, 17), (5, 22), (5, 24),
+    (6, 10), (6, 16), (6, 24),
+    (7, 11), (7, 15),
+    (8, 12), (8, 13),
+];
+
+const LWSS: &[(i32, i32)] = &[
+    (0, 1), (0, 2), (0, 3), (0, 4),
+    (1, 0), (1, 4),
+    (2, 4),
+    (3, 0), (3, 3),
+];
+
+const BLOCK: &[(i32, i32)] = &[(0, 0), (0, 1), (1, 0), (1, 1)];
+
+const BEEHIVE: &[(i32, i32)] = &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)];
+
+impl Pattern {
+    fn offsets(self) -> &'static [(i32, i32)] {
+        match self {
+            Pattern::Glider => GLIDER,
+            Pattern::Pulsar => PULSAR,
+            Pattern::Copperhead => COPPERHEAD,
+            Pattern::GosperGliderGun => GOSPER_GLIDER_GUN,
+            Pattern::Lwss => LWSS,
+            Pattern::Block => BLOCK,
+            Pattern::Beehive => BEEHIVE,
+        }
+    }
+}
+
+/// How neighbors beyond the edge of the grid are treated.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryMode {
+    /// Off-grid neighbors wrap around to the opposite edge.
+    Toroidal,
+    /// Off-grid neighbors are treated as dead rather than wrapping.
+    Dead,
+}
+
 #[wasm_bindgen]
 pub struct Universe {
     width: u32,
     height: u32,
     cells: FixedBitSet,
+    boundary_mode: BoundaryMode,
+    // Preallocated back buffer for `tick`, swapped with `cells` at the end
+    // of each generation so no allocation is needed on the hot path.
+    scratch: FixedBitSet,
+    rule: Rule,
+    // Flat indices of the cells whose state flipped during the last
+    // `tick`, so JS can redraw only the dirty cells instead of the whole
+    // grid.
+    changed: Vec<u32>,
 }
 
 impl Universe {
@@ -32,64 +198,58 @@ impl Universe {
         (row * self.width + col) as usize
     }
 
-    // calculate index for outbound coordinates - needed for convinience
-    fn get_index_signed(&self, row: i32, col: i32) -> usize {
-        self.get_index(
-            ((row % self.height as i32 + self.height as i32) % self.height as i32) as u32,
-            ((col % self.width as i32 + self.width as i32) % self.width as i32) as u32,
-        )
+    // calculate index for outbound coordinates - needed for convinience.
+    // In `Dead` mode, coordinates that fall off the grid have no index;
+    // callers must skip them instead of wrapping.
+    fn get_index_signed(&self, row: i32, col: i32) -> Option<usize> {
+        match self.boundary_mode {
+            BoundaryMode::Toroidal => Some(self.get_index(
+                ((row % self.height as i32 + self.height as i32) % self.height as i32) as u32,
+                ((col % self.width as i32 + self.width as i32) % self.width as i32) as u32,
+            )),
+            BoundaryMode::Dead => {
+                if row < 0 || row >= self.height as i32 || col < 0 || col >= self.width as i32 {
+                    None
+                } else {
+                    Some(self.get_index(row as u32, col as u32))
+                }
+            }
+        }
     }
 
     fn live_neighbor_count(&self, row: u32, col: u32) -> u8 {
-        let mut count = 0;
-
-        let north = if row == 0 {
-            self.height - 1
-        } else {
-            row - 1
+        let (north, south) = match self.boundary_mode {
+            BoundaryMode::Toroidal => (
+                Some(if row == 0 { self.height - 1 } else { row - 1 }),
+                Some(if row == self.height - 1 { 0 } else { row + 1 }),
+            ),
+            BoundaryMode::Dead => (
+                if row == 0 { None } else { Some(row - 1) },
+                if row == self.height - 1 { None } else { Some(row + 1) },
+            ),
         };
 
-        let south = if row == self.height - 1 {
-            0
-        } else {
-            row + 1
+        let (west, east) = match self.boundary_mode {
+            BoundaryMode::Toroidal => (
+                Some(if col == 0 { self.width - 1 } else { col - 1 }),
+                Some(if col == self.width - 1 { 0 } else { col + 1 }),
+            ),
+            BoundaryMode::Dead => (
+                if col == 0 { None } else { Some(col - 1) },
+                if col == self.width - 1 { None } else { Some(col + 1) },
+            ),
         };
 
-        let west = if col == 0 {
-            self.width - 1
-        } else {
-            col - 1
-        };
-
-        let east = if col == self.width - 1 {
-            0
-        } else {
-            col + 1
-        };
-
-        let nw = self.get_index(north, west);
-        count += self.cells[nw] as u8;
-
-        let n = self.get_index(north, col);
-        count += self.cells[n] as u8;
-
-        let ne = self.get_index(north, east);
-        count += self.cells[ne] as u8;
-
-        let w = self.get_index(row, west);
-        count += self.cells[w] as u8;
-
-        let e = self.get_index(row, east);
-        count += self.cells[e] as u8;
-
-        let sw = self.get_index(south, west);
-        count += self.cells[sw] as u8;
-
-        let s = self.get_index(south, col);
-        count += self.cells[s] as u8;
-
-        let se = self.get_index(south, east);
-        count += self.cells[se] as u8;
+        let mut count = 0;
+        for &r in &[north, Some(row), south] {
+            for &c in &[west, Some(col), east] {
+                if let (Some(r), Some(c)) = (r, c) {
+                    if (r, c) != (row, col) {
+                        count += self.cells[self.get_index(r, c)] as u8;
+                    }
+                }
+            }
+        }
 
         count
     }
@@ -100,7 +260,7 @@ impl Universe {
 impl Universe {
     pub fn tick(&mut self) {
         let _timer = Timer::new("Universe::tick");
-        let mut next = self.cells.clone();
+        self.changed.clear();
 
         for row in 0..self.height {
             for col in 0..self.width {
@@ -108,27 +268,19 @@ impl Universe {
                 let idx = self.get_index(row, col);
                 let current_state = self.cells[idx];
 
-                let next_state = match (current_state, live_cnt) {
-                    // Rule 1: Any live cell with fewer than two live neighbours
-                    // dies, as if caused by underpopulation.
-                    (true, n) if n < 2 => false,
-                    // Rule 2: Any live cell with two or three live neighbours
-                    // lives on to the next generation.
-                    (true, 2) | (true, 3) => true,
-                    // Rule 3: Any live cell with more than three live
-                    // neighbours dies, as if by overpopulation.
-                    (true, n) if n > 3 => false,
-                    // Rule 4: Any dead cell with exactly three live neighbours
-                    // becomes a live cell, as if by reproduction.
-                    (false, 3) => true,
-                    // All other cells remain in the same state.
-                    (some_state, _) => some_state,
+                let next_state = if current_state {
+                    self.rule.survival & (1 << live_cnt) != 0
+                } else {
+                    self.rule.birth & (1 << live_cnt) != 0
                 };
-                next.set(idx, next_state);
+                if next_state != current_state {
+                    self.changed.push(idx as u32);
+                }
+                self.scratch.set(idx, next_state);
             }
         }
 
-        self.cells = next
+        std::mem::swap(&mut self.cells, &mut self.scratch);
     }
 
     pub fn new(width: u32, height: u32) -> Universe {
@@ -139,49 +291,212 @@ impl Universe {
             cells.set(idx, Math::random() >= 0.5);
         }
 
+        let scratch = FixedBitSet::with_capacity(size);
+
         Universe {
             width,
             height,
             cells,
+            scratch,
+            rule: Rule::CONWAY,
+            changed: Vec::new(),
+            boundary_mode: BoundaryMode::Toroidal,
         }
     }
 
-    pub fn new_copperhead(width: u32, height: u32) -> Universe {
+    /// Like `new`, but fills the grid from a deterministic xorshift64 PRNG
+    /// seeded by `seed` instead of `Math::random`, so the resulting
+    /// universe is reproducible across runs and machines.
+    pub fn new_seeded(width: u32, height: u32, seed: u64) -> Universe {
         utils::set_panic_hook();
-        let top_offset = 32;
-        let left_offset = 32;
-        let copperhead = [
-            false, true, true, false, false, true, true, false, false, false, false, true, true,
-            false, false, false, false, false, false, true, true, false, false, false, true, false,
-            true, false, false, true, false, true, true, false, false, false, false, false, false,
-            true, false, false, false, false, false, false, false, false, true, false, false,
-            false, false, false, false, true, false, true, true, false, false, true, true, false,
-            false, false, true, true, true, true, false, false, false, false, false, false, false,
-            false, false, false, false, false, false, true, true, false, false, false, false,
-            false, false, true, true, false, false, false,
-        ];
+        let size = (width * height) as usize;
+        let mut cells = FixedBitSet::with_capacity(size);
+        // Seeding with 0 would be a fixed point of xorshift64, so nudge it
+        // to an odd number.
+        let mut state = seed | 1;
+        for idx in 0..size {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            cells.set(idx, state & (1 << 63) != 0);
+        }
+
+        let scratch = FixedBitSet::with_capacity(size);
+
+        Universe {
+            width,
+            height,
+            cells,
+            scratch,
+            rule: Rule::CONWAY,
+            changed: Vec::new(),
+            boundary_mode: BoundaryMode::Toroidal,
+        }
+    }
+
+    /// Decode a pattern in [RLE](https://conwaylife.com/wiki/Run_Length_Encoded)
+    /// format into a new `width` by `height` universe. An optional header
+    /// line (`x = ..., y = ..., rule = ...`) may set the universe's rule.
+    /// If the header's `x`/`y` match the requested `width`/`height`, cells
+    /// are placed at their literal coordinates so that a universe saved
+    /// with `to_rle` and reloaded with `from_rle` round-trips exactly;
+    /// otherwise (no header, or a size mismatch, as when importing a
+    /// pattern smaller than the target universe) the decoded bounding box
+    /// is centered in the grid instead.
+    pub fn from_rle(rle: &str, width: u32, height: u32) -> Universe {
+        let mut rule = Rule::CONWAY;
+        let mut header_dims = None;
+        let mut body = String::new();
+
+        for line in rle.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('x') {
+                let mut header_width = None;
+                let mut header_height = None;
+                for field in line.split(',') {
+                    let mut parts = field.splitn(2, '=');
+                    let key = parts.next().unwrap_or("").trim();
+                    let value = parts.next().unwrap_or("").trim();
+                    match key {
+                        "x" => header_width = value.parse::<u32>().ok(),
+                        "y" => header_height = value.parse::<u32>().ok(),
+                        "rule" => rule = Rule::parse(value),
+                        _ => {}
+                    }
+                }
+                if let (Some(w), Some(h)) = (header_width, header_height) {
+                    header_dims = Some((w, h));
+                }
+                continue;
+            }
+            body.push_str(line);
+        }
+
+        let mut live_cells = Vec::new();
+        let mut row = 0i64;
+        let mut col = 0i64;
+        let mut count = 0u32;
+        let mut min_row = 0i64;
+        let mut max_row = 0i64;
+        let mut min_col = 0i64;
+        let mut max_col = 0i64;
+
+        for c in body.chars() {
+            match c {
+                '0'..='9' => {
+                    count = count * 10 + c.to_digit(10).unwrap();
+                }
+                'b' | 'o' => {
+                    let run = if count == 0 { 1 } else { count };
+                    if c == 'o' {
+                        for i in 0..run as i64 {
+                            live_cells.push((row, col + i));
+                            min_row = min_row.min(row);
+                            max_row = max_row.max(row);
+                            min_col = min_col.min(col + i);
+                            max_col = max_col.max(col + i);
+                        }
+                    }
+                    col += run as i64;
+                    count = 0;
+                }
+                '$' => {
+                    row += if count == 0 { 1 } else { count as i64 };
+                    col = 0;
+                    count = 0;
+                }
+                '!' => break,
+                _ => {}
+            }
+        }
+
+        // A header declaring the same size as the target universe means
+        // this is almost certainly our own `to_rle` output being reloaded,
+        // so preserve absolute coordinates instead of recentering.
+        let (row_offset, col_offset) = if header_dims == Some((width, height)) {
+            (0, 0)
+        } else {
+            let pattern_width = (max_col - min_col + 1).max(1);
+            let pattern_height = (max_row - min_row + 1).max(1);
+            (
+                (height as i64 - pattern_height) / 2 - min_row,
+                (width as i64 - pattern_width) / 2 - min_col,
+            )
+        };
 
         let size = (width * height) as usize;
         let mut cells = FixedBitSet::with_capacity(size);
-        let mut ship_row = 0;
-        for cells_row in top_offset + 1..=top_offset + copperhead.len() / 8 {
-            let row_offset = ship_row * 8;
-            for ship_col in 0..8 {
-                cells.set(
-                    cells_row * width as usize + left_offset + ship_col + 1,
-                    copperhead[row_offset + ship_col],
-                );
+        for (r, c) in live_cells {
+            let r = r + row_offset;
+            let c = c + col_offset;
+            if r >= 0 && r < height as i64 && c >= 0 && c < width as i64 {
+                let idx = (r as u32 * width + c as u32) as usize;
+                cells.set(idx, true);
             }
-            ship_row += 1;
         }
+        let scratch = FixedBitSet::with_capacity(size);
 
         Universe {
             width,
             height,
             cells,
+            scratch,
+            rule,
+            changed: Vec::new(),
+            boundary_mode: BoundaryMode::Toroidal,
         }
     }
 
+    /// Encode the current universe as an [RLE](https://conwaylife.com/wiki/Run_Length_Encoded)
+    /// pattern string, including an `x`/`y`/`rule` header line.
+    pub fn to_rle(&self) -> String {
+        let mut out = format!(
+            "x = {}, y = {}, rule = {}\n",
+            self.width,
+            self.height,
+            self.rule.to_bs_string()
+        );
+
+        for row in 0..self.height {
+            let mut col = 0;
+            while col < self.width {
+                let alive = self.cells[self.get_index(row, col)];
+                let mut run = 1;
+                while col + run < self.width
+                    && self.cells[self.get_index(row, col + run)] == alive
+                {
+                    run += 1;
+                }
+                // A trailing dead run at the end of a row carries no
+                // information, since `$`/`!` already imply dead cells.
+                if alive || col + run != self.width {
+                    out.push_str(&run.to_string());
+                    out.push(if alive { 'o' } else { 'b' });
+                }
+                col += run;
+            }
+            out.push('$');
+        }
+        out.push('!');
+
+        out
+    }
+
+    /// Set the simulation rule from standard B/S notation, e.g. `"B3/S23"`
+    /// for Conway's Life or `"B36/S23"` for HighLife.
+    pub fn set_rule(&mut self, rule: &str) {
+        self.rule = Rule::parse(rule);
+    }
+
+    /// Select whether off-grid neighbors wrap around (`Toroidal`, the
+    /// default) or count as dead (`Dead`).
+    pub fn set_boundary_mode(&mut self, boundary_mode: BoundaryMode) {
+        self.boundary_mode = boundary_mode;
+    }
+
     pub fn render(&self) -> String {
         self.to_string()
     }
@@ -198,14 +513,18 @@ impl Universe {
     /// Resets all cells to the dead state.
     pub fn set_width(&mut self, width: u32) {
         self.width = width;
-        self.cells = FixedBitSet::with_capacity((width * self.height) as usize);
+        let size = (width * self.height) as usize;
+        self.cells = FixedBitSet::with_capacity(size);
+        self.scratch = FixedBitSet::with_capacity(size);
     }
 
     /// Set the height of the universe.
     /// Resets all cells to the dead state.
     pub fn set_height(&mut self, height: u32) {
         self.height = height;
-        self.cells = FixedBitSet::with_capacity((self.width * height) as usize);
+        let size = (self.width * height) as usize;
+        self.cells = FixedBitSet::with_capacity(size);
+        self.scratch = FixedBitSet::with_capacity(size);
     }
 
     pub fn toggle_cell(&mut self, row: u32, col: u32) {
@@ -213,38 +532,34 @@ impl Universe {
         self.cells.toggle(idx);
     }
 
-    pub fn draw_glider(&mut self, center_row: u32, center_col: u32) {
-        let row = center_row as i32;
-        let col = center_col as i32;
-        self.set_cells(&[
-            (row, col),
-            (row - 1, col - 1),
-            (row, col + 1),
-            (row + 1, col),
-            (row + 1, col - 1),
-        ])
-    }
-
-    pub fn draw_pulsar(&mut self, center_row: u32, center_col: u32) {
-        let row = center_row as i32;
-        let col = center_col as i32;
-        self.set_cells(&[
-            (row - 6, col - 4), (row - 6, col - 3), (row - 6, col - 2), (row - 6, col + 4), (row - 6, col + 3), (row - 6, col + 2),
-            (row - 4, col - 6), (row - 4, col - 1), (row - 4, col + 1), (row - 4, col + 6),
-            (row - 3, col - 6), (row - 3, col - 1), (row - 3, col + 1), (row - 3, col + 6),
-            (row - 2, col - 6), (row - 2, col - 1), (row - 2, col + 1), (row - 2, col + 6),
-            (row - 1, col - 4), (row - 1, col - 3), (row - 1, col - 2), (row - 1, col + 4), (row - 1, col + 3), (row - 1, col + 2),
-            (row + 1, col - 4), (row + 1, col - 3), (row + 1, col - 2), (row + 1, col + 4), (row + 1, col + 3), (row + 1, col + 2),
-            (row + 2, col - 6), (row + 2, col - 1), (row + 2, col + 1), (row + 2, col + 6),
-            (row + 3, col - 6), (row + 3, col - 1), (row + 3, col + 1), (row + 3, col + 6),
-            (row + 4, col - 6), (row + 4, col - 1), (row + 4, col + 1), (row + 4, col + 6),
-            (row + 6, col - 4), (row + 6, col - 3), (row + 6, col - 2), (row + 6, col + 4), (row + 6, col + 3), (row + 6, col + 2),
-        ])
+    /// Stamp a named pattern with its anchor at `(row, col)`. Replaces the
+    /// old one-off `draw_glider`/`draw_pulsar`/`new_copperhead` helpers
+    /// with a single, discoverable entry point.
+    pub fn insert_pattern(&mut self, pattern: Pattern, row: u32, col: u32) {
+        let row = row as i32;
+        let col = col as i32;
+        let cells: Vec<(i32, i32)> = pattern
+            .offsets()
+            .iter()
+            .map(|&(dr, dc)| (row + dr, col + dc))
+            .collect();
+        self.set_cells(&cells);
     }
 
     pub fn cells(&self) -> *const u32 {
         self.cells.as_slice().as_ptr()
     }
+
+    /// Flat indices of the cells that flipped state during the last
+    /// `tick`, as a pointer JS can read `changed_cells_len()` `u32`s from,
+    /// so only the dirty cells need to be redrawn.
+    pub fn changed_cells(&self) -> *const u32 {
+        self.changed.as_ptr()
+    }
+
+    pub fn changed_cells_len(&self) -> usize {
+        self.changed.len()
+    }
 }
 
 impl fmt::Display for Universe {
@@ -271,8 +586,9 @@ impl Universe {
     /// of each cell as an array.
     pub fn set_cells(&mut self, cells: &[(i32, i32)]) {
         for (row, col) in cells.iter().cloned() {
-            let idx = self.get_index_signed(row, col);
-            self.cells.set(idx, true);
+            if let Some(idx) = self.get_index_signed(row, col) {
+                self.cells.set(idx, true);
+            }
         }
     }
 }
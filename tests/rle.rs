@@ -0,0 +1,52 @@
+//! Test suite for RLE import/export.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Pattern, Universe};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn live_cells(universe: &Universe) -> Vec<(u32, u32)> {
+    universe
+        .get_cells()
+        .ones()
+        .map(|idx| (idx as u32 / universe.width(), idx as u32 % universe.width()))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn round_trip_preserves_cells() {
+    let mut universe = Universe::from_rle("!", 20, 20);
+    universe.insert_pattern(Pattern::Pulsar, 10, 10);
+    let before = live_cells(&universe);
+
+    let rle = universe.to_rle();
+    let restored = Universe::from_rle(&rle, 20, 20);
+
+    assert_eq!(live_cells(&restored), before);
+}
+
+#[wasm_bindgen_test]
+fn round_trip_preserves_rule() {
+    let mut universe = Universe::from_rle("!", 20, 20);
+    universe.set_rule("B36/S23");
+    universe.insert_pattern(Pattern::Block, 5, 5);
+
+    let rle = universe.to_rle();
+    assert!(rle.starts_with("x = 20, y = 20, rule = B36/S23"));
+
+    let mut restored = Universe::from_rle(&rle, 20, 20);
+    // The decoded rule shows up again in the restored universe's header.
+    assert!(restored.to_rle().starts_with("x = 20, y = 20, rule = B36/S23"));
+
+    let before = live_cells(&restored);
+    // A block is a still life under both B3/S23 and B36/S23, so it stays
+    // put after a tick; this checks that the cells round-tripped alongside
+    // the rule line, not which rule is applied.
+    restored.tick();
+    assert_eq!(live_cells(&restored), before);
+}
@@ -0,0 +1,88 @@
+//! Test suite for the Web and headless browsers.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{BoundaryMode, Pattern, Universe};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn blank_universe(width: u32, height: u32) -> Universe {
+    Universe::from_rle("!", width, height)
+}
+
+fn live_cells(universe: &Universe) -> Vec<(u32, u32)> {
+    universe
+        .get_cells()
+        .ones()
+        .map(|idx| (idx as u32 / universe.width(), idx as u32 % universe.width()))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn block_is_a_still_life() {
+    let mut universe = blank_universe(16, 16);
+    universe.insert_pattern(Pattern::Block, 5, 5);
+    let before = live_cells(&universe);
+
+    universe.tick();
+
+    assert_eq!(live_cells(&universe), before);
+}
+
+#[wasm_bindgen_test]
+fn beehive_is_a_still_life() {
+    let mut universe = blank_universe(16, 16);
+    universe.insert_pattern(Pattern::Beehive, 5, 5);
+    let before = live_cells(&universe);
+
+    universe.tick();
+
+    assert_eq!(live_cells(&universe), before);
+}
+
+#[wasm_bindgen_test]
+fn glider_translates_diagonally() {
+    let mut universe = blank_universe(20, 20);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    universe.insert_pattern(Pattern::Glider, 5, 5);
+    let before = live_cells(&universe);
+
+    // A glider returns to its original shape every 4 ticks, shifted by
+    // (1, 1).
+    for _ in 0..4 {
+        universe.tick();
+    }
+
+    let after = live_cells(&universe);
+    assert_eq!(after.len(), before.len());
+
+    let shifted: Vec<(u32, u32)> = before.iter().map(|&(r, c)| (r + 1, c + 1)).collect();
+    let mut shifted = shifted;
+    let mut after = after;
+    shifted.sort();
+    after.sort();
+    assert_eq!(after, shifted);
+}
+
+#[wasm_bindgen_test]
+fn gosper_glider_gun_emits_a_glider() {
+    let mut universe = blank_universe(60, 40);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    universe.insert_pattern(Pattern::GosperGliderGun, 5, 5);
+
+    // The gun itself spans 9 rows by 36 columns from its anchor and fires
+    // its first glider by generation 30.
+    for _ in 0..30 {
+        universe.tick();
+    }
+
+    let escaped = live_cells(&universe)
+        .into_iter()
+        .any(|(r, c)| r >= 5 + 9 || c >= 5 + 36);
+    assert!(escaped, "expected a glider to have escaped the gun's bounding box");
+}
+